@@ -0,0 +1,15 @@
+//! `uniffi-bindgen` entry point
+//!
+//! Generates Swift/Kotlin/Python/Ruby bindings from the `ffi` module's
+//! `#[uniffi::export]` scaffolding. `ffi.rs` uses proc-macro scaffolding
+//! rather than a `.udl` file, so bindings are generated from the built
+//! cdylib rather than from source, e.g.
+//! `cargo build --release --features uniffi` followed by
+//! `cargo run --bin uniffi-bindgen generate --library target/release/librtodo.so --language python --out-dir bindings`.
+//! Only built when the `uniffi` feature is enabled.
+
+#![cfg(feature = "uniffi")]
+
+fn main() {
+    uniffi::uniffi_bindgen_main()
+}
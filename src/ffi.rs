@@ -0,0 +1,171 @@
+//! FFI module
+//!
+//! Exposes `Table`'s CRUD operations through UniFFI so Swift, Kotlin,
+//! Python, and Ruby can drive rtodo's core logic directly instead of
+//! reimplementing it per platform. Only compiled in when the `uniffi`
+//! feature is enabled.
+//!
+//! UniFFI interface objects are shared across the FFI boundary behind an
+//! `Arc` that UniFFI supplies, so `Table` wraps a `Mutex<CoreTable>` here; every exported fallible
+//! method returns `FfiError` instead of rtodo's internal `String` errors,
+//! since UniFFI needs a concrete error type to generate a per-language
+//! exception type from.
+
+#![cfg(feature = "uniffi")]
+
+use std::sync::Mutex;
+
+use crate::table::{Table as CoreTable, Todo as CoreTodo, TodoStatus as CoreTodoStatus};
+
+/// Error surfaced to FFI callers in place of rtodo's internal `String` errors
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum FfiError {
+    /// No todo exists with the given id
+    #[error("todo not found")]
+    NotFound,
+    /// The given id string isn't a valid UUID
+    #[error("invalid id")]
+    InvalidId,
+}
+
+/// A todo item, as seen across the FFI boundary
+#[derive(uniffi::Record)]
+pub struct Todo {
+    pub title: String,
+    pub description: String,
+    pub status: TodoStatus,
+}
+
+impl From<&CoreTodo> for Todo {
+    fn from(todo: &CoreTodo) -> Self {
+        Todo {
+            title: todo.get_title(),
+            description: todo.get_description(),
+            status: todo.get_status().into(),
+        }
+    }
+}
+
+/// Todo status, as seen across the FFI boundary
+#[derive(uniffi::Enum)]
+pub enum TodoStatus {
+    Unfinished,
+    Finished,
+    Forgave,
+}
+
+impl From<&CoreTodoStatus> for TodoStatus {
+    fn from(status: &CoreTodoStatus) -> Self {
+        match status {
+            CoreTodoStatus::Unfinished => TodoStatus::Unfinished,
+            CoreTodoStatus::Finished => TodoStatus::Finished,
+            CoreTodoStatus::Forgave => TodoStatus::Forgave,
+        }
+    }
+}
+
+/// Todo table, exposed as a UniFFI interface object
+///
+/// Wraps a `CoreTable` in a `Mutex` so the generated bindings can share one
+/// instance across threads, matching how `Arc<Self>` is handed back to
+/// callers by the constructor.
+#[derive(uniffi::Object)]
+pub struct Table {
+    inner: Mutex<CoreTable>,
+}
+
+impl Default for Table {
+    fn default() -> Self {
+        Table::new()
+    }
+}
+
+#[uniffi::export]
+impl Table {
+    /// Creates a new empty table
+    #[uniffi::constructor]
+    pub fn new() -> Self {
+        Table {
+            inner: Mutex::new(CoreTable::new()),
+        }
+    }
+
+    /// Adds a new todo to the table
+    ///
+    /// # Returns
+    ///
+    /// ID of the newly added todo, as a UUID string
+    pub fn add_todo(&self, title: String, description: String) -> String {
+        self.inner
+            .lock()
+            .unwrap()
+            .add_todo(CoreTodo::new(title, description))
+            .to_string()
+    }
+
+    /// Removes a todo by ID
+    ///
+    /// # Errors
+    ///
+    /// Returns `FfiError::InvalidId` if `id` isn't a valid UUID, or
+    /// `FfiError::NotFound` if no todo exists with that ID
+    pub fn remove_todo_by_id(&self, id: String) -> Result<Todo, FfiError> {
+        let id = parse_id(&id)?;
+        self.inner
+            .lock()
+            .unwrap()
+            .remove_todo_by_id(id)
+            .map(|todo| Todo::from(&todo))
+            .ok_or(FfiError::NotFound)
+    }
+
+    /// Replaces a todo's title and description by ID
+    ///
+    /// # Errors
+    ///
+    /// Returns `FfiError::InvalidId` if `id` isn't a valid UUID, or
+    /// `FfiError::NotFound` if no todo exists with that ID
+    pub fn modify_todo_by_id(&self, id: String, title: String, description: String) -> Result<Todo, FfiError> {
+        let id = parse_id(&id)?;
+        self.inner
+            .lock()
+            .unwrap()
+            .modify_todo_by_id(id, CoreTodo::new(title, description))
+            .map(Todo::from)
+            .map_err(|_| FfiError::NotFound)
+    }
+
+    /// Marks a todo as completed
+    ///
+    /// # Errors
+    ///
+    /// Returns `FfiError::InvalidId` if `id` isn't a valid UUID, or
+    /// `FfiError::NotFound` if no todo exists with that ID
+    pub fn finish_todo_by_id(&self, id: String) -> Result<Todo, FfiError> {
+        let id = parse_id(&id)?;
+        let mut guard = self.inner.lock().unwrap();
+        let todo = guard.get_todo_by_id(id).ok_or(FfiError::NotFound)?;
+        todo.finish();
+        Ok(Todo::from(&*todo))
+    }
+
+    /// Gets a todo by ID
+    pub fn get_todo_by_id(&self, id: String) -> Option<Todo> {
+        let id = parse_id(&id).ok()?;
+        self.inner
+            .lock()
+            .unwrap()
+            .get_todo_by_id(id)
+            .map(|todo| Todo::from(&*todo))
+    }
+
+    /// Serializes the table to a JSON string
+    pub fn serialize(&self) -> Option<String> {
+        self.inner.lock().unwrap().serialize()
+    }
+}
+
+/// Parses an FFI-supplied id string into a `Uuid`
+fn parse_id(id: &str) -> Result<uuid::Uuid, FfiError> {
+    uuid::Uuid::parse_str(id).map_err(|_| FfiError::InvalidId)
+}
@@ -9,9 +9,22 @@
 //!
 //! All todo data is persisted in `todo.json` file.
 
+#[cfg(feature = "uniffi")]
+pub mod ffi;
+pub mod schedule;
+#[cfg(feature = "serve")]
+pub mod serve;
+pub mod store;
 pub mod table;
+pub mod taskwarrior;
+pub mod tdo;
 
-pub use table::{Table, Todo};
+pub use store::{Action, Store};
+pub use table::{short_id, Table, Todo};
+pub use tdo::Tdo;
+
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
 
 use clap::Parser;
 
@@ -29,19 +42,94 @@ pub struct Args {
 pub enum Command {
     /// Add a new todo item
     #[clap(about = "Add a new todo item")]
-    Add,
+    Add {
+        /// Name of the list to add to (defaults to the default list)
+        #[arg(long)]
+        list: Option<String>,
+    },
     /// List all todo items
     #[clap(about = "List all todos")]
-    List,
+    List {
+        /// Name of the list to show (defaults to the default list)
+        #[arg(long)]
+        list: Option<String>,
+        /// Only show unfinished todos past their due date
+        #[arg(long)]
+        overdue: bool,
+    },
     /// Remove specified todo item
     #[clap(about = "Remove a todo item")]
-    Remove,
+    Remove {
+        /// Name of the list to remove from (defaults to the default list)
+        #[arg(long)]
+        list: Option<String>,
+    },
     /// Mark todo item as complete
     #[clap(about = "Complete a todo item")]
-    Finish,
+    Finish {
+        /// Name of the list to look the todo up in (defaults to the default list)
+        #[arg(long)]
+        list: Option<String>,
+    },
     /// Mark todo item as abandoned
     #[clap(about = "Abandon a todo item")]
-    Forgive,
+    Forgive {
+        /// Name of the list to look the todo up in (defaults to the default list)
+        #[arg(long)]
+        list: Option<String>,
+    },
+    /// Create a new named list
+    #[clap(about = "Create a new todo list", name = "list-new")]
+    ListNew {
+        /// Name of the list to create
+        name: String,
+    },
+    /// Remove a named list
+    #[clap(about = "Remove a todo list", name = "list-rm")]
+    ListRm {
+        /// Name of the list to remove
+        name: String,
+    },
+    /// Undo the last change to a list
+    #[clap(about = "Undo the last change")]
+    Undo {
+        /// Name of the list to undo in (defaults to the default list)
+        #[arg(long)]
+        list: Option<String>,
+    },
+    /// Redo the last undone change to a list
+    #[clap(about = "Redo the last undone change")]
+    Redo {
+        /// Name of the list to redo in (defaults to the default list)
+        #[arg(long)]
+        list: Option<String>,
+    },
+    /// Run an HTTP server exposing the table
+    #[cfg(feature = "serve")]
+    #[clap(about = "Run an HTTP server exposing the table")]
+    Serve {
+        /// TCP port to listen on
+        #[arg(long, default_value_t = 8080)]
+        port: u16,
+    },
+    /// Import tasks from a Taskwarrior `task export` JSON file
+    #[clap(about = "Import tasks from a Taskwarrior export")]
+    Import {
+        /// Path to a Taskwarrior `task export` JSON file
+        path: String,
+        /// Name of the list to import into (defaults to the default list)
+        #[arg(long)]
+        list: Option<String>,
+    },
+    /// Export a list as Taskwarrior `task import` compatible JSON
+    #[clap(about = "Export a list as Taskwarrior-compatible JSON")]
+    Export {
+        /// Path to write the Taskwarrior JSON export to
+        path: String,
+        /// Name of the list to export (defaults to the default list)
+        #[arg(long)]
+        list: Option<String>,
+    },
 }
 
 /// Main entry function for the program
@@ -54,37 +142,86 @@ pub enum Command {
 /// Returns `std::io::Error` on IO errors
 pub fn run() -> Result<(), std::io::Error> {
     let args = Args::parse();
-    let mut table = init_table();
+
+    #[cfg(feature = "serve")]
+    if let Command::Serve { port } = &args.command {
+        return serve_cmd(init_tdo(), *port);
+    }
+
+    let mut tdo = init_tdo();
     let result = match args.command {
-        Command::Add => add(&mut table),
-        Command::List => list(&table),
-        Command::Remove => remove(&mut table),
-        Command::Finish => finish(&mut table),
-        Command::Forgive => forgive(&mut table),
+        Command::Add { list } => add(&mut tdo, list_name(&list)),
+        Command::List { list, overdue } => list_todos(&tdo, list_name(&list), overdue),
+        Command::Remove { list } => remove(&mut tdo, list_name(&list)),
+        Command::Finish { list } => finish(&mut tdo, list_name(&list)),
+        Command::Forgive { list } => forgive(&mut tdo, list_name(&list)),
+        Command::ListNew { name } => list_new(&mut tdo, name),
+        Command::ListRm { name } => list_rm(&mut tdo, name),
+        Command::Undo { list } => undo(&mut tdo, list_name(&list)),
+        Command::Redo { list } => redo(&mut tdo, list_name(&list)),
+        Command::Import { path, list } => import_taskwarrior(&mut tdo, list_name(&list), &path),
+        Command::Export { path, list } => export_taskwarrior(&tdo, list_name(&list), &path),
+        #[cfg(feature = "serve")]
+        Command::Serve { .. } => unreachable!("handled above"),
     };
-    
+
     // Save changes to file
-    if let Ok(json) = serde_json::to_string_pretty(&table) {
+    if let Ok(json) = serde_json::to_string_pretty(&tdo) {
         std::fs::write("todo.json", json)?;
     }
-    
+
     result
 }
 
-/// Initialize todo table
+/// Resolves the `--list` option to a concrete list name
+///
+/// # Arguments
+///
+/// * `list` - User-supplied list name, or `None` to use the default list
+fn list_name(list: &Option<String>) -> &str {
+    list.as_deref().unwrap_or(tdo::DEFAULT_LIST)
+}
+
+/// Initialize the todo container
 ///
-/// Attempts to load existing todos from todo.json file.
-/// Creates new empty table if file doesn't exist or is unreadable.
+/// Attempts to load existing lists from todo.json file.
+/// Creates a new container with only the default list if the file
+/// doesn't exist or is unreadable.
 ///
 /// # Returns
 ///
-/// Initialized `Table` instance
-fn init_table() -> Table {
+/// Initialized `Tdo` instance
+fn init_tdo() -> Tdo {
     if let Ok(file) = std::fs::File::open("todo.json") {
-        let table: Table = serde_json::from_reader(file).unwrap();
-        return table;
+        let tdo: Tdo = serde_json::from_reader(file).unwrap();
+        return tdo;
+    }
+    Tdo::new()
+}
+
+/// Looks up a named list's store, printing a consistent error when it's missing
+///
+/// # Arguments
+///
+/// * `tdo` - Mutable reference to the todo container
+/// * `name` - Name of the list to look up
+fn list_mut<'a>(tdo: &'a mut Tdo, name: &str) -> Option<&'a mut Store> {
+    if tdo.get_list(name).is_none() {
+        println!("❌ List '{}' not found", name);
+        return None;
     }
-    Table::new()
+    tdo.get_list_mut(name)
+}
+
+/// Resolves the 1-based position shown in `{:>3}` output to a todo's id
+///
+/// # Arguments
+///
+/// * `store` - List to resolve the position against
+/// * `index` - 1-based position, as printed by `Table`'s `Display` impl
+fn resolve_index(store: &Store, index: usize) -> Option<uuid::Uuid> {
+    let position = index.checked_sub(1)?;
+    store.state().list_todos().get(position).map(|(id, _)| *id)
 }
 
 /// Add new todo item
@@ -93,12 +230,13 @@ fn init_table() -> Table {
 ///
 /// # Arguments
 ///
-/// * `table` - Mutable reference to todo table
+/// * `tdo` - Mutable reference to the todo container
+/// * `list` - Name of the list to add to
 ///
 /// # Errors
 ///
 /// Returns `std::io::Error` on stdin read failure
-fn add(table: &mut Table) -> Result<(), std::io::Error> {
+fn add(tdo: &mut Tdo, list: &str) -> Result<(), std::io::Error> {
     println!("Enter todo title:");
     let mut title = String::new();
     std::io::stdin().read_line(&mut title)?;
@@ -109,9 +247,27 @@ fn add(table: &mut Table) -> Result<(), std::io::Error> {
     std::io::stdin().read_line(&mut description)?;
     let description = description.trim().to_string();
 
-    let todo = Todo::new(title, description);
-    let id = table.add_todo(todo);
-    println!("✅ Successfully added todo #{}", id);
+    println!("Enter due date (optional, e.g. tomorrow, next monday, in 3 days, 2024-06-01):");
+    let mut due_input = String::new();
+    std::io::stdin().read_line(&mut due_input)?;
+    let due_input = due_input.trim();
+    let due = if due_input.is_empty() {
+        None
+    } else {
+        let due = schedule::parse_due(due_input, chrono::Utc::now()).ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "Unrecognized due date")
+        })?;
+        Some(due)
+    };
+
+    let Some(store) = list_mut(tdo, list) else {
+        return Ok(());
+    };
+
+    let mut todo = Todo::new(title, description);
+    todo.set_due(due);
+    let id = store.dispatch(Action::Add(todo));
+    println!("✅ Successfully added todo [{}]", short_id(&id));
     Ok(())
 }
 
@@ -119,95 +275,302 @@ fn add(table: &mut Table) -> Result<(), std::io::Error> {
 ///
 /// # Arguments
 ///
-/// * `table` - Reference to todo table
+/// * `tdo` - Reference to the todo container
+/// * `list` - Name of the list to show
+/// * `overdue` - When `true`, only show unfinished todos past their due date
 ///
 /// # Errors
 ///
 /// Returns `std::io::Error` on stdout write failure
-fn list(table: &Table) -> Result<(), std::io::Error> {
-    println!("{}", table);
+fn list_todos(tdo: &Tdo, list: &str, overdue: bool) -> Result<(), std::io::Error> {
+    let Some(store) = tdo.get_list(list) else {
+        println!("❌ List '{}' not found", list);
+        return Ok(());
+    };
+
+    if !overdue {
+        println!("{}", store.state());
+        return Ok(());
+    }
+
+    let overdue_todos = store.state().overdue(chrono::Utc::now());
+    if overdue_todos.is_empty() {
+        println!("📝 No overdue todos");
+    } else {
+        println!("📝 Overdue todos:");
+        for (i, (id, todo)) in overdue_todos.into_iter().enumerate() {
+            println!("{:>3}. [{}] {}", i + 1, short_id(&id), todo);
+        }
+    }
     Ok(())
 }
 
 /// Remove specified todo item
 ///
-/// Reads todo ID from stdin and removes it from the table.
+/// Reads the todo's displayed position (the `{:>3}` column) from stdin and
+/// removes it from the table.
 ///
 /// # Arguments
 ///
-/// * `table` - Mutable reference to todo table
+/// * `tdo` - Mutable reference to the todo container
+/// * `list` - Name of the list to remove from
 ///
 /// # Errors
 ///
-/// Returns `std::io::Error` on stdin read failure or invalid ID format
-fn remove(table: &mut Table) -> Result<(), std::io::Error> {
-    println!("Enter todo ID to remove:");
+/// Returns `std::io::Error` on stdin read failure or invalid number format
+fn remove(tdo: &mut Tdo, list: &str) -> Result<(), std::io::Error> {
+    println!("Enter todo number to remove:");
     let mut input = String::new();
     std::io::stdin().read_line(&mut input)?;
-    let id = input.trim().parse::<usize>().map_err(|_| {
-        std::io::Error::new(std::io::ErrorKind::InvalidInput, "Invalid ID")
+    let index = input.trim().parse::<usize>().map_err(|_| {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, "Invalid number")
     })?;
 
-    if let Some(_) = table.remove_todo_by_id(id) {
-        println!("✅ Successfully removed todo #{}", id);
-    } else {
-        println!("❌ Todo #{} not found", id);
+    let Some(store) = list_mut(tdo, list) else {
+        return Ok(());
+    };
+
+    match resolve_index(store, index) {
+        Some(id) => {
+            store.dispatch(Action::Remove(id));
+            println!("✅ Successfully removed todo #{}", index);
+        }
+        None => println!("❌ Todo #{} not found", index),
     }
     Ok(())
 }
 
 /// Mark todo item as complete
 ///
-/// Reads todo ID from stdin and updates its status to completed.
+/// Reads the todo's displayed position (the `{:>3}` column) from stdin and
+/// updates its status to completed.
 ///
 /// # Arguments
 ///
-/// * `table` - Mutable reference to todo table
+/// * `tdo` - Mutable reference to the todo container
+/// * `list` - Name of the list to look the todo up in
 ///
 /// # Errors
 ///
-/// Returns `std::io::Error` on stdin read failure or invalid ID format
-fn finish(table: &mut Table) -> Result<(), std::io::Error> {
-    println!("Enter todo ID to complete:");
+/// Returns `std::io::Error` on stdin read failure or invalid number format
+fn finish(tdo: &mut Tdo, list: &str) -> Result<(), std::io::Error> {
+    println!("Enter todo number to complete:");
     let mut input = String::new();
     std::io::stdin().read_line(&mut input)?;
-    let id = input.trim().parse::<usize>().map_err(|_| {
-        std::io::Error::new(std::io::ErrorKind::InvalidInput, "Invalid ID")
+    let index = input.trim().parse::<usize>().map_err(|_| {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, "Invalid number")
     })?;
 
-    if let Some(todo) = table.get_todo_by_id(id) {
-        todo.finish();
-        println!("✅ Marked todo #{} as completed", id);
-    } else {
-        println!("❌ Todo #{} not found", id);
+    let Some(store) = list_mut(tdo, list) else {
+        return Ok(());
+    };
+
+    match resolve_index(store, index) {
+        Some(id) => {
+            let finished = store.state().get_todo(id).map(|todo| todo.get_status())
+                == Some(&table::TodoStatus::Finished);
+            if !finished {
+                store.dispatch(Action::Toggle(id));
+            }
+            println!("✅ Marked todo #{} as completed", index);
+        }
+        None => println!("❌ Todo #{} not found", index),
     }
     Ok(())
 }
 
 /// Mark todo item as abandoned
 ///
-/// Reads todo ID from stdin and updates its status to abandoned.
+/// Reads the todo's displayed position (the `{:>3}` column) from stdin and
+/// updates its status to abandoned.
 ///
 /// # Arguments
 ///
-/// * `table` - Mutable reference to todo table
+/// * `tdo` - Mutable reference to the todo container
+/// * `list` - Name of the list to look the todo up in
 ///
 /// # Errors
 ///
-/// Returns `std::io::Error` on stdin read failure or invalid ID format
-fn forgive(table: &mut Table) -> Result<(), std::io::Error> {
-    println!("Enter todo ID to abandon:");
+/// Returns `std::io::Error` on stdin read failure or invalid number format
+fn forgive(tdo: &mut Tdo, list: &str) -> Result<(), std::io::Error> {
+    println!("Enter todo number to abandon:");
     let mut input = String::new();
     std::io::stdin().read_line(&mut input)?;
-    let id = input.trim().parse::<usize>().map_err(|_| {
-        std::io::Error::new(std::io::ErrorKind::InvalidInput, "Invalid ID")
+    let index = input.trim().parse::<usize>().map_err(|_| {
+        std::io::Error::new(std::io::ErrorKind::InvalidInput, "Invalid number")
     })?;
 
-    if let Some(todo) = table.get_todo_by_id(id) {
-        todo.forgive();
-        println!("✅ Marked todo #{} as abandoned", id);
+    let Some(store) = list_mut(tdo, list) else {
+        return Ok(());
+    };
+
+    match resolve_index(store, index) {
+        Some(id) => {
+            store.dispatch(Action::Forgive(id));
+            println!("✅ Marked todo #{} as abandoned", index);
+        }
+        None => println!("❌ Todo #{} not found", index),
+    }
+    Ok(())
+}
+
+/// Creates a new named list
+///
+/// # Arguments
+///
+/// * `tdo` - Mutable reference to the todo container
+/// * `name` - Name of the list to create
+///
+/// # Errors
+///
+/// Returns `std::io::Error` on stdout write failure
+fn list_new(tdo: &mut Tdo, name: String) -> Result<(), std::io::Error> {
+    match tdo.create_list(name.clone()) {
+        Ok(()) => println!("✅ Created list '{}'", name),
+        Err(e) => println!("❌ {}", e),
+    }
+    Ok(())
+}
+
+/// Removes a named list
+///
+/// # Arguments
+///
+/// * `tdo` - Mutable reference to the todo container
+/// * `name` - Name of the list to remove
+///
+/// # Errors
+///
+/// Returns `std::io::Error` on stdout write failure
+fn list_rm(tdo: &mut Tdo, name: String) -> Result<(), std::io::Error> {
+    match tdo.remove_list(&name) {
+        Ok(_) => println!("✅ Removed list '{}'", name),
+        Err(e) => println!("❌ {}", e),
+    }
+    Ok(())
+}
+
+/// Undoes the last change to a list
+///
+/// # Arguments
+///
+/// * `tdo` - Mutable reference to the todo container
+/// * `list` - Name of the list to undo in
+///
+/// # Errors
+///
+/// Returns `std::io::Error` on stdout write failure
+fn undo(tdo: &mut Tdo, list: &str) -> Result<(), std::io::Error> {
+    let Some(store) = list_mut(tdo, list) else {
+        return Ok(());
+    };
+
+    if store.undo() {
+        println!("✅ Undid last change to '{}'", list);
     } else {
-        println!("❌ Todo #{} not found", id);
+        println!("❌ Nothing to undo in '{}'", list);
+    }
+    Ok(())
+}
+
+/// Redoes the last undone change to a list
+///
+/// # Arguments
+///
+/// * `tdo` - Mutable reference to the todo container
+/// * `list` - Name of the list to redo in
+///
+/// # Errors
+///
+/// Returns `std::io::Error` on stdout write failure
+fn redo(tdo: &mut Tdo, list: &str) -> Result<(), std::io::Error> {
+    let Some(store) = list_mut(tdo, list) else {
+        return Ok(());
+    };
+
+    if store.redo() {
+        println!("✅ Redid last undone change to '{}'", list);
+    } else {
+        println!("❌ Nothing to redo in '{}'", list);
+    }
+    Ok(())
+}
+
+/// Imports a Taskwarrior `task export` JSON file into a list
+///
+/// # Arguments
+///
+/// * `tdo` - Mutable reference to the todo container
+/// * `list` - Name of the list to import into
+/// * `path` - Path to the Taskwarrior JSON file
+///
+/// # Errors
+///
+/// Returns `std::io::Error` on file read failure
+fn import_taskwarrior(tdo: &mut Tdo, list: &str, path: &str) -> Result<(), std::io::Error> {
+    let json = std::fs::read_to_string(path)?;
+    let todos = match taskwarrior::import_tasks(&json) {
+        Ok(todos) => todos,
+        Err(e) => {
+            println!("❌ {}", e);
+            return Ok(());
+        }
+    };
+
+    let Some(store) = list_mut(tdo, list) else {
+        return Ok(());
+    };
+
+    let count = todos.len();
+    for todo in todos {
+        store.dispatch(Action::Add(todo));
     }
+    println!("✅ Imported {} task(s) into '{}'", count, list);
     Ok(())
+}
+
+/// Exports a list as Taskwarrior `task import` compatible JSON
+///
+/// # Arguments
+///
+/// * `tdo` - Reference to the todo container
+/// * `list` - Name of the list to export
+/// * `path` - Path to write the JSON export to
+///
+/// # Errors
+///
+/// Returns `std::io::Error` on file write failure
+fn export_taskwarrior(tdo: &Tdo, list: &str, path: &str) -> Result<(), std::io::Error> {
+    let Some(store) = tdo.get_list(list) else {
+        println!("❌ List '{}' not found", list);
+        return Ok(());
+    };
+
+    let json = match taskwarrior::export_tasks(&store.state().list_todos()) {
+        Ok(json) => json,
+        Err(e) => {
+            println!("❌ {}", e);
+            return Ok(());
+        }
+    };
+
+    std::fs::write(path, json)?;
+    println!("✅ Exported '{}' to {}", list, path);
+    Ok(())
+}
+
+/// Runs the HTTP server mode, blocking until the process is terminated
+///
+/// # Arguments
+///
+/// * `tdo` - Initial todo container state, shared with the server
+/// * `port` - TCP port to listen on
+///
+/// # Errors
+///
+/// Returns `std::io::Error` if the server can't bind the given port
+#[cfg(feature = "serve")]
+fn serve_cmd(tdo: Tdo, port: u16) -> Result<(), std::io::Error> {
+    let shared = std::sync::Arc::new(std::sync::Mutex::new(tdo));
+    serve::run(shared, port)
 }
\ No newline at end of file
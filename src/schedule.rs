@@ -0,0 +1,171 @@
+//! Schedule module
+//!
+//! Parses human-friendly due date input such as "tomorrow", "next monday",
+//! "in 3 days", or an ISO date like "2024-06-01" into a concrete
+//! `DateTime<Utc>`. Resolution proceeds through a small sequence of
+//! matchers, from most to least specific, and falls back to ISO parsing
+//! when nothing else matches.
+//!
+//! # Examples
+//!
+//! ```
+//! use rtodo::schedule::parse_due;
+//! use chrono::Utc;
+//!
+//! let now = Utc::now();
+//! assert!(parse_due("tomorrow", now).is_some());
+//! assert!(parse_due("not a date", now).is_none());
+//! ```
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc, Weekday};
+
+/// Parses human-friendly due date input relative to `now`
+///
+/// # Arguments
+///
+/// * `input` - Human-friendly date text, e.g. "tomorrow" or "2024-06-01"
+/// * `now` - Current time, used to resolve relative dates
+///
+/// # Returns
+///
+/// The resolved `DateTime<Utc>`, or `None` if `input` cannot be parsed
+pub fn parse_due(input: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let normalized = input.trim().to_lowercase();
+    if normalized.is_empty() {
+        return None;
+    }
+
+    parse_relative_day(&normalized, now)
+        .or_else(|| parse_weekday(&normalized, now))
+        .or_else(|| parse_in_duration(&normalized, now))
+        .or_else(|| parse_iso_date(&normalized))
+}
+
+/// Matches `today`/`tomorrow`/`yesterday`, resolved against `now`'s date
+fn parse_relative_day(input: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let today = now.date_naive();
+    let date = match input {
+        "today" => today,
+        "tomorrow" => today + Duration::days(1),
+        "yesterday" => today - Duration::days(1),
+        _ => return None,
+    };
+    Some(end_of_day(date))
+}
+
+/// Matches a weekday name (optionally prefixed with "next"), resolved to
+/// its next future occurrence
+fn parse_weekday(input: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let name = input.strip_prefix("next ").unwrap_or(input);
+    let target = match name {
+        "monday" => Weekday::Mon,
+        "tuesday" => Weekday::Tue,
+        "wednesday" => Weekday::Wed,
+        "thursday" => Weekday::Thu,
+        "friday" => Weekday::Fri,
+        "saturday" => Weekday::Sat,
+        "sunday" => Weekday::Sun,
+        _ => return None,
+    };
+
+    let mut date = now.date_naive().succ_opt()?;
+    while date.weekday() != target {
+        date = date.succ_opt()?;
+    }
+    Some(end_of_day(date))
+}
+
+/// Matches `in <n> <unit>` where unit is one of day/week/hour/month
+fn parse_in_duration(input: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let parts: Vec<&str> = input.split_whitespace().collect();
+    let [prefix, amount, unit] = parts[..] else {
+        return None;
+    };
+    if prefix != "in" {
+        return None;
+    }
+
+    let amount: i64 = amount.parse().ok()?;
+    let duration = match unit.trim_end_matches('s') {
+        "hour" => Duration::try_hours(amount)?,
+        "day" => Duration::try_days(amount)?,
+        "week" => Duration::try_weeks(amount)?,
+        "month" => Duration::try_days(amount.checked_mul(30)?)?,
+        _ => return None,
+    };
+    now.checked_add_signed(duration)
+}
+
+/// Falls back to ISO `%Y-%m-%d` parsing
+fn parse_iso_date(input: &str) -> Option<DateTime<Utc>> {
+    let date = NaiveDate::parse_from_str(input, "%Y-%m-%d").ok()?;
+    Some(end_of_day(date))
+}
+
+/// Resolves a date to the end of that day in UTC
+fn end_of_day(date: NaiveDate) -> DateTime<Utc> {
+    Utc.from_utc_datetime(&date.and_hms_opt(23, 59, 59).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_today_tomorrow_yesterday() {
+        assert_eq!(
+            parse_due("today", now()).unwrap().date_naive(),
+            now().date_naive()
+        );
+        assert_eq!(
+            parse_due("tomorrow", now()).unwrap().date_naive(),
+            now().date_naive() + Duration::days(1)
+        );
+        assert_eq!(
+            parse_due("yesterday", now()).unwrap().date_naive(),
+            now().date_naive() - Duration::days(1)
+        );
+    }
+
+    #[test]
+    fn test_next_weekday() {
+        // now() is a Wednesday; "next monday" should be 5 days out
+        let resolved = parse_due("next monday", now()).unwrap();
+        assert_eq!(resolved.weekday(), Weekday::Mon);
+        assert!(resolved.date_naive() > now().date_naive());
+    }
+
+    #[test]
+    fn test_in_n_units() {
+        assert_eq!(
+            parse_due("in 3 days", now()).unwrap(),
+            now() + Duration::days(3)
+        );
+        assert_eq!(
+            parse_due("in 2 hours", now()).unwrap(),
+            now() + Duration::hours(2)
+        );
+    }
+
+    #[test]
+    fn test_iso_fallback() {
+        let resolved = parse_due("2024-06-01", now()).unwrap();
+        assert_eq!(resolved.date_naive(), NaiveDate::from_ymd_opt(2024, 6, 1).unwrap());
+    }
+
+    #[test]
+    fn test_unparsable_input_rejected() {
+        assert_eq!(parse_due("whenever", now()), None);
+        assert_eq!(parse_due("", now()), None);
+    }
+
+    #[test]
+    fn test_out_of_range_duration_rejected_not_panicked() {
+        assert_eq!(parse_due("in 100000000000000 months", now()), None);
+        assert_eq!(parse_due("in 9999999999999999 days", now()), None);
+    }
+}
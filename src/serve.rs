@@ -0,0 +1,418 @@
+//! Serve module
+//!
+//! Boots a small HTTP server over a shared `Tdo`, so rtodo can back a web
+//! UI or be driven by scripts instead of only interactive stdin. Only
+//! compiled in when the `serve` feature is enabled.
+//!
+//! Routes (all under `?list=<name>`, defaulting to the default list):
+//! * `GET /todos` - list todos, paginated via `limit`/`offset` query params
+//! * `POST /todos` - create a todo from a JSON body
+//! * `PUT /todos/:id` - modify, finish, or forgive a todo
+//! * `DELETE /todos/:id` - remove a todo
+//!
+//! Every mutating request is dispatched through the targeted list's
+//! `Store` (so it shows up in that list's undo history) and persisted to
+//! `todo.json` immediately, so the CLI and server always see the same
+//! data.
+
+#![cfg(feature = "serve")]
+
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+
+use tiny_http::{Header, Method, Request, Response, Server};
+use uuid::Uuid;
+
+use crate::store::Action;
+use crate::table::Todo;
+use crate::tdo::{Tdo, DEFAULT_LIST};
+
+type HttpResponse = Response<Cursor<Vec<u8>>>;
+
+/// Pagination options for `GET /todos`
+///
+/// # Fields
+///
+/// * `limit` - Maximum number of todos to return
+/// * `offset` - Number of todos to skip before taking `limit`
+#[derive(Debug, Default)]
+pub struct ListOptions {
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+impl ListOptions {
+    /// Applies offset/limit to a list of todos: skip `offset`, then take `limit`
+    fn apply<'a>(&self, items: Vec<(Uuid, &'a Todo)>) -> Vec<(Uuid, &'a Todo)> {
+        items
+            .into_iter()
+            .skip(self.offset.unwrap_or(0))
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect()
+    }
+}
+
+/// A todo paired with its list id, as returned by `GET /todos`
+#[derive(serde::Serialize)]
+struct TodoEntry<'a> {
+    id: Uuid,
+    #[serde(flatten)]
+    todo: &'a Todo,
+}
+
+/// Body accepted by `POST /todos`
+#[derive(serde::Deserialize)]
+struct CreateBody {
+    title: String,
+    description: String,
+}
+
+/// Body accepted by `PUT /todos/:id`
+#[derive(serde::Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum UpdateBody {
+    Modify { title: String, description: String },
+    Finish,
+    Forgive,
+}
+
+/// Runs the HTTP server, blocking until the process is terminated
+///
+/// # Arguments
+///
+/// * `tdo` - Shared todo container, mutated in place by each request
+/// * `port` - TCP port to listen on
+///
+/// # Errors
+///
+/// Returns `std::io::Error` if the server can't bind the given port
+pub fn run(tdo: Arc<Mutex<Tdo>>, port: u16) -> std::io::Result<()> {
+    let server = Server::http(("0.0.0.0", port))
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::AddrInUse, e.to_string()))?;
+    println!("🚀 Serving rtodo on http://0.0.0.0:{}", port);
+
+    for request in server.incoming_requests() {
+        handle(&tdo, request);
+    }
+    Ok(())
+}
+
+/// Routes and handles a single HTTP request
+fn handle(tdo: &Arc<Mutex<Tdo>>, mut request: Request) {
+    let (path, query) = split_url(request.url());
+    let segments: Vec<&str> = path.as_str()
+        .trim_matches('/')
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect();
+    let list = query
+        .get("list")
+        .cloned()
+        .unwrap_or_else(|| DEFAULT_LIST.to_string());
+
+    let response = match (request.method(), segments.as_slice()) {
+        (Method::Get, ["todos"]) => handle_list(tdo, &list, &query),
+        (Method::Post, ["todos"]) => handle_create(tdo, &list, &mut request),
+        (Method::Put, ["todos", id]) => handle_update(tdo, &list, id, &mut request),
+        (Method::Delete, ["todos", id]) => handle_delete(tdo, &list, id),
+        _ => text_response(404, "Not found"),
+    };
+
+    let _ = request.respond(response);
+}
+
+/// Handles `GET /todos`
+fn handle_list(tdo: &Arc<Mutex<Tdo>>, list: &str, query: &HashMap<String, String>) -> HttpResponse {
+    let opts = ListOptions {
+        limit: query.get("limit").and_then(|v| v.parse().ok()),
+        offset: query.get("offset").and_then(|v| v.parse().ok()),
+    };
+
+    let tdo = tdo.lock().unwrap();
+    let Some(store) = tdo.get_list(list) else {
+        return text_response(404, "List not found");
+    };
+
+    let entries: Vec<TodoEntry> = opts
+        .apply(store.state().list_todos())
+        .into_iter()
+        .map(|(id, todo)| TodoEntry { id, todo })
+        .collect();
+    json_response(200, &entries)
+}
+
+/// Handles `POST /todos`
+fn handle_create(tdo: &Arc<Mutex<Tdo>>, list: &str, request: &mut Request) -> HttpResponse {
+    let Some(create) = read_json_body::<CreateBody>(request) else {
+        return text_response(400, "Invalid body");
+    };
+    create_todo(tdo, list, create)
+}
+
+/// Adds a todo to `list` from an already-parsed `POST /todos` body
+fn create_todo(tdo: &Arc<Mutex<Tdo>>, list: &str, create: CreateBody) -> HttpResponse {
+    let mut tdo = tdo.lock().unwrap();
+    let Some(store) = tdo.get_list_mut(list) else {
+        return text_response(404, "List not found");
+    };
+
+    let id = store.dispatch(Action::Add(Todo::new(create.title, create.description)));
+    persist(&tdo);
+    json_response(201, &serde_json::json!({ "id": id }))
+}
+
+/// Handles `PUT /todos/:id`
+fn handle_update(tdo: &Arc<Mutex<Tdo>>, list: &str, id: &str, request: &mut Request) -> HttpResponse {
+    let Some(id) = parse_todo_id(id) else {
+        return text_response(400, "Invalid id");
+    };
+    let Some(update) = read_json_body::<UpdateBody>(request) else {
+        return text_response(400, "Invalid body");
+    };
+    update_todo(tdo, list, id, update)
+}
+
+/// Applies an already-parsed `PUT /todos/:id` body to `id` in `list`
+fn update_todo(tdo: &Arc<Mutex<Tdo>>, list: &str, id: Uuid, update: UpdateBody) -> HttpResponse {
+    let mut tdo = tdo.lock().unwrap();
+    let Some(store) = tdo.get_list_mut(list) else {
+        return text_response(404, "List not found");
+    };
+    if store.state().get_todo(id).is_none() {
+        return text_response(404, "Todo not found");
+    }
+
+    let action = match update {
+        UpdateBody::Modify { title, description } => Action::Modify(id, Todo::new(title, description)),
+        UpdateBody::Finish => {
+            if store.state().get_todo(id).map(|todo| todo.get_status())
+                == Some(&crate::table::TodoStatus::Finished)
+            {
+                return text_response(200, "OK");
+            }
+            Action::Toggle(id)
+        }
+        UpdateBody::Forgive => Action::Forgive(id),
+    };
+    store.dispatch(action);
+    persist(&tdo);
+    text_response(200, "OK")
+}
+
+/// Handles `DELETE /todos/:id`
+fn handle_delete(tdo: &Arc<Mutex<Tdo>>, list: &str, id: &str) -> HttpResponse {
+    let Some(id) = parse_todo_id(id) else {
+        return text_response(400, "Invalid id");
+    };
+    delete_todo(tdo, list, id)
+}
+
+/// Removes `id` from `list`
+fn delete_todo(tdo: &Arc<Mutex<Tdo>>, list: &str, id: Uuid) -> HttpResponse {
+    let mut tdo = tdo.lock().unwrap();
+    let Some(store) = tdo.get_list_mut(list) else {
+        return text_response(404, "List not found");
+    };
+    if store.state().get_todo(id).is_none() {
+        return text_response(404, "Todo not found");
+    }
+
+    store.dispatch(Action::Remove(id));
+    persist(&tdo);
+    text_response(200, "OK")
+}
+
+/// Parses a `:id` path segment into a todo id
+fn parse_todo_id(id: &str) -> Option<Uuid> {
+    Uuid::parse_str(id).ok()
+}
+
+/// Reads and JSON-decodes a request body, returning `None` on any failure
+fn read_json_body<T: serde::de::DeserializeOwned>(request: &mut Request) -> Option<T> {
+    let mut body = String::new();
+    request.as_reader().read_to_string(&mut body).ok()?;
+    serde_json::from_str(&body).ok()
+}
+
+/// Persists the whole container to `todo.json`, best-effort
+fn persist(tdo: &Tdo) {
+    if let Ok(json) = serde_json::to_string_pretty(tdo) {
+        let _ = std::fs::write("todo.json", json);
+    }
+}
+
+/// Splits a request URL into its path and parsed query parameters
+fn split_url(url: &str) -> (String, HashMap<String, String>) {
+    match url.split_once('?') {
+        Some((path, query)) => (path.to_string(), parse_query(query)),
+        None => (url.to_string(), HashMap::new()),
+    }
+}
+
+/// Parses a `key=value&key=value` query string
+fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
+
+/// Builds a plain-text response with the given status code
+fn text_response(status: u16, body: &str) -> HttpResponse {
+    Response::from_string(body).with_status_code(status)
+}
+
+/// Builds a JSON response with the given status code
+fn json_response<T: serde::Serialize>(status: u16, body: &T) -> HttpResponse {
+    let json = serde_json::to_string(body).unwrap_or_else(|_| "null".to_string());
+    let header = Header::from_bytes(&b"Content-Type"[..], &b"application/json"[..]).unwrap();
+    Response::from_string(json)
+        .with_status_code(status)
+        .with_header(header)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shared(tdo: Tdo) -> Arc<Mutex<Tdo>> {
+        Arc::new(Mutex::new(tdo))
+    }
+
+    fn body_of(response: HttpResponse) -> String {
+        String::from_utf8(response.into_reader().into_inner()).unwrap()
+    }
+
+    #[test]
+    fn test_list_options_apply_limit_and_offset() {
+        let a = Todo::new("a".to_string(), "".to_string());
+        let b = Todo::new("b".to_string(), "".to_string());
+        let c = Todo::new("c".to_string(), "".to_string());
+        let items = vec![(a.id, &a), (b.id, &b), (c.id, &c)];
+
+        let opts = ListOptions { limit: Some(1), offset: Some(1) };
+        let page = opts.apply(items);
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].0, b.id);
+    }
+
+    #[test]
+    fn test_list_options_apply_defaults_to_everything() {
+        let a = Todo::new("a".to_string(), "".to_string());
+        let items = vec![(a.id, &a)];
+        assert_eq!(ListOptions::default().apply(items).len(), 1);
+    }
+
+    #[test]
+    fn test_parse_todo_id() {
+        let id = Uuid::new_v4();
+        assert_eq!(parse_todo_id(&id.to_string()), Some(id));
+        assert_eq!(parse_todo_id("not-a-uuid"), None);
+    }
+
+    #[test]
+    fn test_handle_list_unknown_list_is_404() {
+        let tdo = shared(Tdo::new());
+        let response = handle_list(&tdo, "missing", &HashMap::new());
+        assert_eq!(response.status_code(), 404);
+    }
+
+    #[test]
+    fn test_create_todo_adds_to_list() {
+        let tdo = shared(Tdo::new());
+        let response = create_todo(
+            &tdo,
+            DEFAULT_LIST,
+            CreateBody { title: "title".to_string(), description: "description".to_string() },
+        );
+        assert_eq!(response.status_code(), 201);
+        assert_eq!(tdo.lock().unwrap().get_list(DEFAULT_LIST).unwrap().state().list_todos().len(), 1);
+    }
+
+    #[test]
+    fn test_create_todo_unknown_list_is_404() {
+        let tdo = shared(Tdo::new());
+        let response = create_todo(
+            &tdo,
+            "missing",
+            CreateBody { title: "title".to_string(), description: "description".to_string() },
+        );
+        assert_eq!(response.status_code(), 404);
+    }
+
+    #[test]
+    fn test_update_todo_missing_todo_is_404() {
+        let tdo = shared(Tdo::new());
+        let response = update_todo(&tdo, DEFAULT_LIST, Uuid::new_v4(), UpdateBody::Finish);
+        assert_eq!(response.status_code(), 404);
+    }
+
+    #[test]
+    fn test_update_todo_unknown_list_is_404() {
+        let tdo = shared(Tdo::new());
+        let response = update_todo(&tdo, "missing", Uuid::new_v4(), UpdateBody::Finish);
+        assert_eq!(response.status_code(), 404);
+    }
+
+    #[test]
+    fn test_update_todo_finish_marks_completed() {
+        let mut tdo = Tdo::new();
+        let id = tdo
+            .get_list_mut(DEFAULT_LIST)
+            .unwrap()
+            .dispatch(Action::Add(Todo::new("title".to_string(), "description".to_string())));
+        let shared_tdo = shared(tdo);
+
+        let response = update_todo(&shared_tdo, DEFAULT_LIST, id, UpdateBody::Finish);
+        assert_eq!(response.status_code(), 200);
+        let locked = shared_tdo.lock().unwrap();
+        let todo = locked.get_list(DEFAULT_LIST).unwrap().state().get_todo(id).unwrap();
+        assert_eq!(todo.get_status(), &crate::table::TodoStatus::Finished);
+    }
+
+    #[test]
+    fn test_delete_todo_removes_it() {
+        let mut tdo = Tdo::new();
+        let id = tdo
+            .get_list_mut(DEFAULT_LIST)
+            .unwrap()
+            .dispatch(Action::Add(Todo::new("title".to_string(), "description".to_string())));
+        let shared_tdo = shared(tdo);
+
+        let response = delete_todo(&shared_tdo, DEFAULT_LIST, id);
+        assert_eq!(response.status_code(), 200);
+        let locked = shared_tdo.lock().unwrap();
+        assert!(locked.get_list(DEFAULT_LIST).unwrap().state().get_todo(id).is_none());
+    }
+
+    #[test]
+    fn test_delete_todo_missing_todo_is_404() {
+        let tdo = shared(Tdo::new());
+        let response = delete_todo(&tdo, DEFAULT_LIST, Uuid::new_v4());
+        assert_eq!(response.status_code(), 404);
+    }
+
+    #[test]
+    fn test_delete_todo_unknown_list_is_404() {
+        let tdo = shared(Tdo::new());
+        let response = delete_todo(&tdo, "missing", Uuid::new_v4());
+        assert_eq!(response.status_code(), 404);
+    }
+
+    #[test]
+    fn test_split_url_parses_query() {
+        let (path, query) = split_url("/todos?list=work&limit=5");
+        assert_eq!(path, "/todos");
+        assert_eq!(query.get("list"), Some(&"work".to_string()));
+        assert_eq!(query.get("limit"), Some(&"5".to_string()));
+    }
+
+    #[test]
+    fn test_json_response_serializes_body() {
+        let response = json_response(200, &serde_json::json!({ "ok": true }));
+        assert_eq!(response.status_code(), 200);
+        assert_eq!(body_of(response), r#"{"ok":true}"#);
+    }
+}
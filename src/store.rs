@@ -0,0 +1,478 @@
+//! Store module
+//!
+//! This module routes every mutation of a `Table` through a single
+//! `dispatch` entry point backed by an `Action`/`reducer` pair, and keeps
+//! an undo/redo history of dispatched actions so an accidental remove or
+//! finish can be undone.
+//!
+//! # Examples
+//!
+//! ```
+//! use rtodo::store::{Action, Store};
+//! use rtodo::table::Todo;
+//!
+//! let mut store = Store::new();
+//! let id = store.dispatch(Action::Add(Todo::new("title".to_string(), "description".to_string())));
+//!
+//! store.dispatch(Action::Toggle(id));
+//! assert!(store.undo());
+//! assert!(store.state().get_todo(id).is_some());
+//! ```
+
+use serde::{Deserialize, Deserializer, Serialize};
+use uuid::Uuid;
+
+use crate::table::{migrate_table_todos, LegacyIdMap, Table, Todo, TodoStatus};
+
+/// A single state-changing operation on a `Table`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Action {
+    /// Add a new todo
+    Add(Todo),
+    /// Remove a todo by id
+    Remove(Uuid),
+    /// Toggle a todo between finished and unfinished
+    Toggle(Uuid),
+    /// Replace a todo's title/description by id
+    Modify(Uuid, Todo),
+    /// Mark a todo as abandoned
+    Forgive(Uuid),
+}
+
+/// Applies an `Action` to a `Table`, returning the resulting state
+///
+/// # Arguments
+///
+/// * `state` - Table state before the action
+/// * `action` - Action to apply
+///
+/// # Returns
+///
+/// New `Table` reflecting the action; unknown ids are ignored rather than
+/// treated as errors, mirroring the previous direct `Table` methods
+pub fn reducer(state: &Table, action: Action) -> Table {
+    let mut next = state.clone();
+    match action {
+        Action::Add(todo) => {
+            next.add_todo(todo);
+        }
+        Action::Remove(id) => {
+            next.remove_todo_by_id(id);
+        }
+        Action::Toggle(id) => {
+            if let Some(todo) = next.get_todo_by_id(id) {
+                match todo.get_status() {
+                    TodoStatus::Finished => todo.unfinish(),
+                    _ => todo.finish(),
+                }
+            }
+        }
+        Action::Modify(id, new_todo) => {
+            let _ = next.modify_todo_by_id(id, new_todo);
+        }
+        Action::Forgive(id) => {
+            if let Some(todo) = next.get_todo_by_id(id) {
+                todo.forgive();
+            }
+        }
+    }
+    next
+}
+
+/// Keeps a `Table` alongside an undo/redo history of dispatched actions
+///
+/// # Fields
+///
+/// * `state` - Current table state
+/// * `history` - Actions applied so far, oldest first
+/// * `redo_stack` - Actions undone so far, most recently undone last
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Store {
+    state: Table,
+    history: Vec<Action>,
+    redo_stack: Vec<Action>,
+}
+
+impl Store {
+    /// Creates a new store wrapping an empty table
+    pub fn new() -> Store {
+        Store::from_table(Table::new())
+    }
+
+    /// Creates a new store wrapping an existing table with empty history
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - Table to adopt as the initial state
+    pub fn from_table(table: Table) -> Store {
+        Store {
+            state: table,
+            history: Vec::new(),
+            redo_stack: Vec::new(),
+        }
+    }
+
+    /// Gets the current table state
+    pub fn state(&self) -> &Table {
+        &self.state
+    }
+
+    /// Gets a mutable reference to the current table state
+    ///
+    /// This bypasses the action/undo history and should only be used for
+    /// read-modify patterns where an equivalent `Action` doesn't apply.
+    pub fn state_mut(&mut self) -> &mut Table {
+        &mut self.state
+    }
+
+    /// Dispatches an action, applying it to the state and recording it
+    ///
+    /// Pushes the action onto the undo history and clears the redo stack,
+    /// since dispatching a new action invalidates any previously undone
+    /// actions.
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - Action to apply
+    ///
+    /// # Returns
+    ///
+    /// The id the action targets: the new todo's id for `Add`, or the
+    /// targeted todo's id for every other variant
+    pub fn dispatch(&mut self, action: Action) -> Uuid {
+        let id = match &action {
+            Action::Add(todo) => todo.id,
+            Action::Remove(id) | Action::Toggle(id) | Action::Forgive(id) => *id,
+            Action::Modify(id, _) => *id,
+        };
+        self.state = reducer(&self.state, action.clone());
+        self.history.push(action);
+        self.redo_stack.clear();
+        id
+    }
+
+    /// Undoes the last dispatched action
+    ///
+    /// Reconstructs the prior state by replaying every remaining action
+    /// from an empty table, then pushes the undone action onto the redo
+    /// stack.
+    ///
+    /// # Returns
+    ///
+    /// `true` if an action was undone, `false` if the history was empty
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            Some(action) => {
+                self.state = Self::replay(&self.history);
+                self.redo_stack.push(action);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Redoes the most recently undone action
+    ///
+    /// # Returns
+    ///
+    /// `true` if an action was redone, `false` if the redo stack was empty
+    pub fn redo(&mut self) -> bool {
+        match self.redo_stack.pop() {
+            Some(action) => {
+                self.state = reducer(&self.state, action.clone());
+                self.history.push(action);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Replays a sequence of actions from an empty table
+    fn replay(history: &[Action]) -> Table {
+        let mut table = Table::new();
+        for action in history {
+            table = reducer(&table, action.clone());
+        }
+        table
+    }
+}
+
+impl Default for Store {
+    fn default() -> Self {
+        Store::new()
+    }
+}
+
+/// Pre-UUID shape of the non-`Add` `Action` variants: these reference the
+/// old monotonic `usize` todo id instead of a `Uuid`
+///
+/// `Add` doesn't need a shim here since it carries a full `Todo` and no id
+/// reference to translate; `translate_action` special-cases it instead.
+#[derive(Deserialize)]
+enum LegacyAction {
+    Remove(usize),
+    Toggle(usize),
+    Modify(usize, Todo),
+    Forgive(usize),
+}
+
+impl<'de> Deserialize<'de> for Store {
+    /// Deserializes a `Store`, translating any pre-UUID `usize` action ids
+    /// into the same `Uuid`s minted while migrating `state`'s `Table`
+    ///
+    /// A naive `#[derive(Deserialize)]` would let `state`'s `Table` and
+    /// every `Action::Add` embedded in `history`/`redo_stack` each mint an
+    /// independent id for the very same pre-UUID todo, leaving them
+    /// referring to it by different ids. Routing every action id through
+    /// `state`'s own id-translation table (see `migrate_table_todos`) keeps
+    /// them consistent.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Raw {
+            state: serde_json::Value,
+            history: Vec<serde_json::Value>,
+            redo_stack: Vec<serde_json::Value>,
+        }
+        let raw = Raw::deserialize(deserializer)?;
+        let (todos, mut id_map) =
+            migrate_table_todos(raw.state).map_err(serde::de::Error::custom)?;
+
+        // Actions in the order they were originally dispatched: `history`,
+        // followed by whatever was since undone (`redo_stack` is stored
+        // most-recently-undone last, i.e. latest-dispatched first, so reverse
+        // it back to chronological order), so `Add`s get assigned legacy ids in the same sequence
+        // the old monotonic `next_id` counter would have.
+        let history_len = raw.history.len();
+        let mut next_legacy_id = 0usize;
+        let translated = raw
+            .history
+            .into_iter()
+            .chain(raw.redo_stack.into_iter().rev())
+            .map(|action| translate_action(action, &mut id_map, &mut next_legacy_id))
+            .collect::<Result<Vec<Action>, String>>()
+            .map_err(serde::de::Error::custom)?;
+
+        let (history, redo_chronological) = translated.split_at(history_len);
+        let mut redo_stack = redo_chronological.to_vec();
+        redo_stack.reverse();
+
+        Ok(Store {
+            state: Table::from_todos(todos),
+            history: history.to_vec(),
+            redo_stack,
+        })
+    }
+}
+
+/// Translates one raw `Action` JSON value, mapping a legacy `usize` id
+/// through `id_map` (minting and recording a translation for any id not
+/// already present, e.g. one a later-removed todo used)
+fn translate_action(
+    value: serde_json::Value,
+    id_map: &mut LegacyIdMap,
+    next_legacy_id: &mut usize,
+) -> Result<Action, String> {
+    // `Todo`'s own `Deserialize` mints a free-standing id when one is
+    // missing, so `Add` can't be told apart from a genuinely current action
+    // by trying `Action`'s derived `Deserialize` the way every other
+    // variant below can; check explicitly for a pre-UUID payload instead.
+    if let Some(todo_value) = value.get("Add") {
+        let mut todo: Todo = serde_json::from_value(todo_value.clone()).map_err(|e| e.to_string())?;
+        if todo_value.get("id").is_none() {
+            todo.id = *id_map.entry(*next_legacy_id).or_insert(todo.id);
+            *next_legacy_id += 1;
+        }
+        return Ok(Action::Add(todo));
+    }
+
+    if let Ok(action) = serde_json::from_value::<Action>(value.clone()) {
+        return Ok(action);
+    }
+
+    let legacy: LegacyAction = serde_json::from_value(value).map_err(|e| e.to_string())?;
+    Ok(match legacy {
+        LegacyAction::Remove(id) => Action::Remove(legacy_uuid(id_map, id)),
+        LegacyAction::Toggle(id) => Action::Toggle(legacy_uuid(id_map, id)),
+        LegacyAction::Modify(id, todo) => Action::Modify(legacy_uuid(id_map, id), todo),
+        LegacyAction::Forgive(id) => Action::Forgive(legacy_uuid(id_map, id)),
+    })
+}
+
+/// Looks up (or mints and records) the `Uuid` a legacy `usize` id maps to
+fn legacy_uuid(id_map: &mut LegacyIdMap, legacy_id: usize) -> Uuid {
+    *id_map.entry(legacy_id).or_insert_with(Uuid::new_v4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn todo(title: &str) -> Todo {
+        Todo::new(title.to_string(), "description".to_string())
+    }
+
+    #[test]
+    fn test_dispatch_add() {
+        let mut store = Store::new();
+        let id = store.dispatch(Action::Add(todo("title")));
+        assert!(store.state().get_todo(id).is_some());
+    }
+
+    #[test]
+    fn test_undo_redo_remove() {
+        let mut store = Store::new();
+        let id = store.dispatch(Action::Add(todo("title")));
+        store.dispatch(Action::Remove(id));
+        assert!(store.state().get_todo(id).is_none());
+
+        assert!(store.undo());
+        assert!(store.state().get_todo(id).is_some());
+
+        assert!(store.redo());
+        assert!(store.state().get_todo(id).is_none());
+    }
+
+    #[test]
+    fn test_dispatch_clears_redo_stack() {
+        let mut store = Store::new();
+        let id = store.dispatch(Action::Add(todo("title")));
+        store.dispatch(Action::Remove(id));
+        store.undo();
+
+        store.dispatch(Action::Add(todo("other")));
+        assert!(!store.redo());
+    }
+
+    #[test]
+    fn test_undo_with_empty_history_is_noop() {
+        let mut store = Store::new();
+        assert!(!store.undo());
+    }
+
+    #[test]
+    fn test_toggle() {
+        let mut store = Store::new();
+        let id = store.dispatch(Action::Add(todo("title")));
+        store.dispatch(Action::Toggle(id));
+        assert_eq!(store.state().get_todo(id).unwrap().get_status(), &TodoStatus::Finished);
+
+        store.dispatch(Action::Toggle(id));
+        assert_eq!(store.state().get_todo(id).unwrap().get_status(), &TodoStatus::Unfinished);
+    }
+
+    #[test]
+    fn test_deserialize_pre_uuid_store_with_history() {
+        // A `Store` as written by a pre-UUID binary: `state` is keyed by the
+        // old monotonic `usize` id, and `history` holds an `Action::Add` with
+        // a `Todo` that has no `id` field at all.
+        let json = r#"{
+            "state": {
+                "todos": {
+                    "0": {
+                        "title": "title",
+                        "description": "description",
+                        "status": "Unfinished",
+                        "created": "2024-01-01T00:00:00Z"
+                    }
+                },
+                "next_id": 1
+            },
+            "history": [
+                {
+                    "Add": {
+                        "title": "title",
+                        "description": "description",
+                        "status": "Unfinished",
+                        "created": "2024-01-01T00:00:00Z"
+                    }
+                }
+            ],
+            "redo_stack": []
+        }"#;
+
+        let store: Store = serde_json::from_str(json).unwrap();
+        assert_eq!(store.state().list_todos().len(), 1);
+        assert!(store.state().list_todos()[0].1.title == "title");
+    }
+
+    #[test]
+    fn test_deserialize_pre_uuid_store_with_legacy_action_ids() {
+        // A `Store` as written by a pre-UUID binary after a toggle:
+        // `history` has an `Add` followed by a `Toggle` referencing the
+        // old usize id, exactly as the old monotonic `next_id` counter
+        // would have produced.
+        let json = r#"{
+            "state": {
+                "todos": {
+                    "0": {
+                        "title": "title",
+                        "description": "description",
+                        "status": "Finished",
+                        "created": "2024-01-01T00:00:00Z"
+                    }
+                },
+                "next_id": 1
+            },
+            "history": [
+                {
+                    "Add": {
+                        "title": "title",
+                        "description": "description",
+                        "status": "Unfinished",
+                        "created": "2024-01-01T00:00:00Z"
+                    }
+                },
+                { "Toggle": 0 }
+            ],
+            "redo_stack": []
+        }"#;
+
+        let store: Store = serde_json::from_str(json).unwrap();
+        let (id, _) = store.state().list_todos()[0];
+
+        // The `Toggle` in history must reference the very same id `state`
+        // has, not an independently-minted one.
+        match &store.history[1] {
+            Action::Toggle(toggled_id) => assert_eq!(*toggled_id, id),
+            other => panic!("expected Toggle, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_deserialize_pre_uuid_store_ids_stable_across_undo_redo() {
+        let json = r#"{
+            "state": {
+                "todos": {
+                    "0": {
+                        "title": "title",
+                        "description": "description",
+                        "status": "Unfinished",
+                        "created": "2024-01-01T00:00:00Z"
+                    }
+                },
+                "next_id": 1
+            },
+            "history": [
+                {
+                    "Add": {
+                        "title": "title",
+                        "description": "description",
+                        "status": "Unfinished",
+                        "created": "2024-01-01T00:00:00Z"
+                    }
+                }
+            ],
+            "redo_stack": []
+        }"#;
+
+        let mut store: Store = serde_json::from_str(json).unwrap();
+        let id = store.state().list_todos()[0].0;
+
+        assert!(store.undo());
+        assert!(store.redo());
+        assert_eq!(store.state().list_todos()[0].0, id);
+    }
+}
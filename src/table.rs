@@ -7,6 +7,12 @@
 //! - Retrieving todos
 //! - Serializing todo tables
 //!
+//! Todos are keyed by a stable `Uuid` (see `Todo::id`) rather than a
+//! position, so an id never collides or shifts when a list is merged,
+//! imported, or synced across machines. The `{:>3}` column shown by
+//! `Display` is a positional index for CLI convenience only; it is
+//! recomputed on every call to `list_todos` and isn't stored anywhere.
+//!
 //! # Examples
 //!
 //! ```
@@ -42,22 +48,27 @@
 
 mod todo;
 
-pub use crate::table::todo::Todo;
-use serde::{Deserialize, Serialize};
+pub use crate::table::todo::{Todo, TodoStatus};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::{collections::BTreeMap, fmt::Display};
+use uuid::Uuid;
 
 /// Todo table structure
 ///
-/// Uses `BTreeMap` for storage to maintain ID ordering
-/// 
+/// Uses `BTreeMap` for storage, keyed by each todo's stable `Uuid`
+///
 /// # Fields
 ///
-/// * `todos` - Map storing all todos (ID as key)
-/// * `next_id` - Next todo ID counter
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// * `todos` - Map storing all todos (id as key)
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct Table {
-    todos: BTreeMap<usize, Todo>,
-    next_id: usize,
+    todos: BTreeMap<Uuid, Todo>,
+}
+
+impl Default for Table {
+    fn default() -> Self {
+        Table::new()
+    }
 }
 
 impl Table {
@@ -77,7 +88,6 @@ impl Table {
     pub fn new() -> Table {
         Table {
             todos: BTreeMap::new(),
-            next_id: 0,
         }
     }
 
@@ -89,7 +99,7 @@ impl Table {
     ///
     /// # Returns
     ///
-    /// ID of the newly added todo
+    /// Id of the newly added todo (`todo.id`)
     ///
     /// # Examples
     ///
@@ -100,10 +110,10 @@ impl Table {
     /// let todo = Todo::new("Test".to_string(), "This is a test".to_string());
     /// let id = table.add_todo(todo);
     /// ```
-    pub fn add_todo(&mut self, todo: Todo) -> usize {
-        self.todos.insert(self.next_id, todo);
-        self.next_id += 1;
-        self.next_id - 1
+    pub fn add_todo(&mut self, todo: Todo) -> Uuid {
+        let id = todo.id;
+        self.todos.insert(id, todo);
+        id
     }
 
     /// Removes a todo by ID
@@ -129,7 +139,7 @@ impl Table {
     ///     println!("Successfully removed todo");
     /// }
     /// ```
-    pub fn remove_todo_by_id(&mut self, id: usize) -> Option<Todo> {
+    pub fn remove_todo_by_id(&mut self, id: Uuid) -> Option<Todo> {
         self.todos.remove(&id)
     }
 
@@ -159,7 +169,7 @@ impl Table {
     ///     Err(e) => println!("Modification failed: {}", e),
     /// }
     /// ```
-    pub fn modify_todo_by_id(&mut self, id: usize, new_todo: Todo) -> Result<&Todo, String> {
+    pub fn modify_todo_by_id(&mut self, id: Uuid, new_todo: Todo) -> Result<&Todo, String> {
         if let Some(todo) = self.todos.get_mut(&id) {
             todo.modify_title(new_todo.title);
             todo.modify_description(new_todo.description);
@@ -192,10 +202,72 @@ impl Table {
     ///     todo.finish(); // Mark todo as complete
     /// }
     /// ```
-    pub fn get_todo_by_id(&mut self, id: usize) -> Option<&mut Todo> {
+    pub fn get_todo_by_id(&mut self, id: Uuid) -> Option<&mut Todo> {
         self.todos.get_mut(&id)
     }
 
+    /// Gets an immutable reference to a todo by ID
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - ID of todo to retrieve
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some(&Todo)` if found, otherwise `None`
+    pub fn get_todo(&self, id: Uuid) -> Option<&Todo> {
+        self.todos.get(&id)
+    }
+
+    /// Lists all todos in creation order
+    ///
+    /// Creation order (rather than id order, which is random for UUIDs) is
+    /// what the CLI's positional `{:>3}` index is built from, so it stays
+    /// stable the way the old monotonic ids used to.
+    ///
+    /// # Returns
+    ///
+    /// `(id, &Todo)` pairs, oldest first
+    pub fn list_todos(&self) -> Vec<(Uuid, &Todo)> {
+        let mut items: Vec<(Uuid, &Todo)> = self.todos.iter().map(|(id, todo)| (*id, todo)).collect();
+        items.sort_by(|(id_a, a), (id_b, b)| a.created.cmp(&b.created).then(id_a.cmp(id_b)));
+        items
+    }
+
+    /// Lists todos that are unfinished and past their due date
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - Current time to compare due dates against
+    ///
+    /// # Returns
+    ///
+    /// Overdue `(id, &Todo)` pairs, oldest first
+    pub fn overdue(&self, now: chrono::DateTime<chrono::Utc>) -> Vec<(Uuid, &Todo)> {
+        self.list_todos()
+            .into_iter()
+            .filter(|(_, todo)| todo.is_overdue(now))
+            .collect()
+    }
+
+    /// Lists all todos sorted by due date, soonest first
+    ///
+    /// Todos without a due date sort last.
+    ///
+    /// # Returns
+    ///
+    /// `(id, &Todo)` pairs sorted by due date
+    pub fn sort_by_due(&self) -> Vec<(Uuid, &Todo)> {
+        let mut items = self.list_todos();
+        items.sort_by(|(_, a), (_, b)| match (a.get_due(), b.get_due()) {
+            (Some(a), Some(b)) => a.cmp(&b),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+        items
+    }
+
     /// Serializes the table to JSON string
     ///
     /// # Returns
@@ -216,13 +288,27 @@ impl Table {
     /// }
     /// ```
     pub fn serialize(&self) -> Option<String> {
-        match serde_json::to_string_pretty(self) {
-            Ok(string) => Some(string),
-            Err(_) => None,
-        }
+        serde_json::to_string_pretty(self).ok()
+    }
+
+    /// Constructs a table directly from an id-keyed map, bypassing `add_todo`
+    ///
+    /// Used by `Store`'s `Deserialize` to assemble `state` from a todos map
+    /// it migrated itself via `migrate_table_todos`.
+    pub(crate) fn from_todos(todos: BTreeMap<Uuid, Todo>) -> Table {
+        Table { todos }
     }
 }
 
+/// Renders a todo id as a short, human-friendly hex prefix
+///
+/// # Arguments
+///
+/// * `id` - Todo id to shorten
+pub fn short_id(id: &Uuid) -> String {
+    id.simple().to_string()[..8].to_string()
+}
+
 impl Display for Table {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.todos.is_empty() {
@@ -230,9 +316,79 @@ impl Display for Table {
         }
 
         writeln!(f, "ðŸ“ Todo list:")?;
-        for (id, todo) in self.todos.iter() {
-            writeln!(f, "{:>3}. {}", id, todo)?;
+        for (i, (id, todo)) in self.list_todos().into_iter().enumerate() {
+            writeln!(f, "{:>3}. [{}] {}", i + 1, short_id(&id), todo)?;
         }
         Ok(())
     }
+}
+
+/// Pre-UUID on-disk shape of a `Table`, keyed by the old monotonic `usize` id
+///
+/// `Todo`'s own `Deserialize` already mints a fresh id for entries that have
+/// no `id` field (see `Todo`'s `#[serde(default = "Uuid::new_v4")]`), so this
+/// shim only needs to account for the map being keyed by the old `usize` id
+/// instead of `Uuid`.
+#[derive(Deserialize)]
+struct LegacyTable {
+    todos: BTreeMap<usize, Todo>,
+    #[allow(dead_code)]
+    next_id: usize,
+}
+
+/// Current on-disk shape of a `Table`, keyed by `Uuid`
+#[derive(Deserialize)]
+struct CurrentTable {
+    todos: BTreeMap<Uuid, Todo>,
+}
+
+impl<'de> Deserialize<'de> for Table {
+    /// Deserializes a `Table`, minting fresh `Uuid`s for any pre-UUID
+    /// `todo.json` file loaded from disk
+    ///
+    /// `#[serde(untagged)]` can't be used to pick between `CurrentTable` and
+    /// `LegacyTable` here: serde's untagged-enum machinery deserializes each
+    /// candidate from a buffered `Content` tree, which (unlike serde_json's
+    /// normal map-key handling) doesn't coerce a JSON object key like `"0"`
+    /// into a `usize`, so `LegacyTable` would fail to parse even for
+    /// genuinely old data. Buffering through `serde_json::Value` instead
+    /// keeps serde_json's own key coercion in the loop.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let (todos, _) = migrate_table_todos(value).map_err(serde::de::Error::custom)?;
+        Ok(Table { todos })
+    }
+}
+
+/// Legacy `usize` id -> `Uuid` translation produced while migrating a
+/// pre-UUID `Table` (empty if the table was already current)
+pub(crate) type LegacyIdMap = BTreeMap<usize, Uuid>;
+
+/// Migrates a raw `Table` JSON value, returning the migrated todos map
+/// alongside the legacy id translation used for any entries that had to be
+/// migrated
+///
+/// Exposed (rather than folded entirely into `Table`'s `Deserialize`) so
+/// `Store`'s `Deserialize` can translate `Action`/history ids through the
+/// exact same mapping, instead of minting an independent, mismatched id for
+/// the same todo wherever it's referenced from `history`/`redo_stack`.
+pub(crate) fn migrate_table_todos(
+    value: serde_json::Value,
+) -> Result<(BTreeMap<Uuid, Todo>, LegacyIdMap), String> {
+    if let Ok(CurrentTable { todos }) = serde_json::from_value(value.clone()) {
+        return Ok((todos, BTreeMap::new()));
+    }
+    let LegacyTable { todos, .. } = serde_json::from_value(value).map_err(|e| e.to_string())?;
+    let mut id_map = BTreeMap::new();
+    let todos = todos
+        .into_iter()
+        .map(|(old_id, todo)| {
+            id_map.insert(old_id, todo.id);
+            (todo.id, todo)
+        })
+        .collect();
+    Ok((todos, id_map))
 }
\ No newline at end of file
@@ -1,22 +1,23 @@
 //! Todo module
-//! 
+//!
 //! This module defines the data structure and operations for individual todos, including:
 //! - Creating new todos
 //! - Getting and modifying todo titles and descriptions
 //! - Managing todo statuses (completed, abandoned, etc.)
-//! 
+//! - Scheduling a due date and checking whether a todo is overdue
+//!
 //! # Examples
-//! 
+//!
 //! ```
 //! use rtodo::table::Todo;
-//! 
+//!
 //! // Create new todo
 //! let mut todo = Todo::new("Write code".to_string(), "Complete Todo project".to_string());
-//! 
+//!
 //! // Modify todo
 //! todo.modify_title("Refactor code".to_string());
 //! todo.modify_description("Optimize code structure".to_string());
-//! 
+//!
 //! // Update status
 //! todo.finish(); // Mark as completed
 //! todo.unfinish(); // Reset to incomplete
@@ -24,22 +25,36 @@
 //! ```
 
 use std::fmt::Display;
+
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 /// Todo structure
 ///
-/// Contains basic todo information: title, description, and status
+/// Contains basic todo information: a stable id, title, description,
+/// status, and optional scheduling information
 ///
 /// # Fields
 ///
+/// * `id` - Stable identifier, unique across lists and machines
 /// * `title` - Todo title
 /// * `description` - Detailed description
 /// * `status` - Current status
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// * `created` - Timestamp the todo was created
+/// * `due` - Optional due date
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Todo {
+    /// Mints a fresh id when deserializing a pre-UUID `Todo` that has no
+    /// `id` field, e.g. one embedded in an old `Store`'s undo/redo history
+    #[serde(default = "Uuid::new_v4")]
+    pub id: Uuid,
     pub title: String,
     pub description: String,
     pub status: TodoStatus,
+    pub created: DateTime<Utc>,
+    #[serde(default)]
+    pub due: Option<DateTime<Utc>>,
 }
 
 /// Todo status enumeration
@@ -48,7 +63,7 @@ pub struct Todo {
 /// * `Unfinished` - Incomplete
 /// * `Finished` - Completed
 /// * `Forgave` - Abandoned
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TodoStatus {
     Unfinished,
     Finished,
@@ -75,7 +90,8 @@ impl Todo {
     ///
     /// # Returns
     ///
-    /// New `Todo` instance with initial status set to Unfinished
+    /// New `Todo` instance with a freshly minted id, initial status set to
+    /// Unfinished, created now, and no due date
     ///
     /// # Examples
     ///
@@ -87,9 +103,12 @@ impl Todo {
     /// ```
     pub fn new(title: String, description: String) -> Todo {
         Todo {
+            id: Uuid::new_v4(),
             title,
             description,
             status: TodoStatus::Unfinished,
+            created: Utc::now(),
+            due: None,
         }
     }
 
@@ -120,6 +139,11 @@ impl Todo {
         &self.status
     }
 
+    /// Gets the due date, if any
+    pub fn get_due(&self) -> Option<DateTime<Utc>> {
+        self.due
+    }
+
     /// Modifies todo title
     ///
     /// # Arguments
@@ -138,6 +162,15 @@ impl Todo {
         self.description = desc;
     }
 
+    /// Sets or clears the due date
+    ///
+    /// # Arguments
+    ///
+    /// * `due` - New due date, or `None` to clear it
+    pub fn set_due(&mut self, due: Option<DateTime<Utc>>) {
+        self.due = due;
+    }
+
     /// Marks todo as completed
     pub fn finish(&mut self) {
         self.status = TodoStatus::Finished
@@ -152,11 +185,24 @@ impl Todo {
     pub fn unfinish(&mut self) {
         self.status = TodoStatus::Unfinished
     }
+
+    /// Checks whether this todo is unfinished and past its due date
+    ///
+    /// # Arguments
+    ///
+    /// * `now` - Current time to compare against
+    ///
+    /// # Returns
+    ///
+    /// `true` if the todo has a due date in the past and is still unfinished
+    pub fn is_overdue(&self, now: DateTime<Utc>) -> bool {
+        matches!(self.due, Some(due) if due < now && self.status == TodoStatus::Unfinished)
+    }
 }
 
 impl Display for Todo {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "[{}] {} ({})", 
+        write!(f, "[{}] {} ({})",
             match self.status {
                 TodoStatus::Unfinished => "Unfinished",
                 TodoStatus::Finished => "Finished",
@@ -164,10 +210,37 @@ impl Display for Todo {
             },
             self.title,
             self.description
-        )
+        )?;
+        if let Some(due) = self.due {
+            write!(f, " - {}", relative_hint(due, Utc::now()))?;
+        }
+        Ok(())
+    }
+}
+
+/// Renders a due date relative to now, e.g. "due in 2h" or "overdue 1d"
+fn relative_hint(due: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let delta = due - now;
+    if delta.num_seconds() >= 0 {
+        format!("due in {}", format_duration(delta))
+    } else {
+        format!("overdue {}", format_duration(-delta))
     }
 }
 
+/// Formats a non-negative `Duration` as the coarsest whole unit that fits
+fn format_duration(delta: chrono::Duration) -> String {
+    let days = delta.num_days();
+    if days >= 1 {
+        return format!("{}d", days);
+    }
+    let hours = delta.num_hours();
+    if hours >= 1 {
+        return format!("{}h", hours);
+    }
+    format!("{}m", delta.num_minutes().max(0))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,11 +250,31 @@ mod tests {
         let title = "title".to_string();
         let description = "description".to_string();
         let todo = Todo::new(title.clone(), description.clone());
-        assert_eq!(todo, Todo {
-            title: title,
-            description: description,
-            status: TodoStatus::Unfinished,
-        })
+        assert_eq!(todo.title, title);
+        assert_eq!(todo.description, description);
+        assert_eq!(todo.status, TodoStatus::Unfinished);
+        assert_eq!(todo.due, None);
+    }
+
+    #[test]
+    fn test_new_assigns_unique_ids() {
+        let a = Todo::new("a".to_string(), "description".to_string());
+        let b = Todo::new("b".to_string(), "description".to_string());
+        assert_ne!(a.id, b.id);
+    }
+
+    #[test]
+    fn test_deserialize_mints_id_when_missing() {
+        let json = r#"{
+            "title": "title",
+            "description": "description",
+            "status": "Unfinished",
+            "created": "2024-01-01T00:00:00Z"
+        }"#;
+        let a: Todo = serde_json::from_str(json).unwrap();
+        let b: Todo = serde_json::from_str(json).unwrap();
+        assert_ne!(a.id, b.id);
+        assert_eq!(a.due, None);
     }
 
     #[test]
@@ -206,4 +299,17 @@ mod tests {
         todo.modify_description(description.clone());
         assert_eq!(todo.description, description)
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_is_overdue() {
+        let mut todo = Todo::new("title".to_string(), "description".to_string());
+        let now = Utc::now();
+        assert!(!todo.is_overdue(now));
+
+        todo.set_due(Some(now - chrono::Duration::days(1)));
+        assert!(todo.is_overdue(now));
+
+        todo.finish();
+        assert!(!todo.is_overdue(now));
+    }
+}
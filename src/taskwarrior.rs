@@ -0,0 +1,256 @@
+//! Taskwarrior bridge module
+//!
+//! Converts between rtodo's `Todo` and the JSON produced/consumed by
+//! Taskwarrior's `task export`/`task import` commands, so existing
+//! Taskwarrior users can migrate their tasks without re-entering them.
+//!
+//! Taskwarrior has no field for a free-form description distinct from its
+//! own `description` (which maps to rtodo's `title`), so the bridge keeps
+//! the task's `uuid` and any `annotations` inside rtodo's `description`
+//! field, prefixed with `taskwarrior:`. Exporting reads that prefix back
+//! out; todos without it (created directly in rtodo) are given a fresh
+//! uuid and have their whole description folded into a single annotation,
+//! so no information is lost on the round trip.
+//!
+//! # Examples
+//!
+//! ```
+//! use rtodo::taskwarrior::{export_tasks, import_tasks};
+//!
+//! let json = r#"[{"uuid":"b1b1b1b1-0000-0000-0000-000000000001","description":"Pay rent","status":"pending"}]"#;
+//! let todos = import_tasks(json).unwrap();
+//! assert_eq!(todos[0].title, "Pay rent");
+//!
+//! let todos: Vec<(uuid::Uuid, &rtodo::table::Todo)> = todos.iter().map(|t| (t.id, t)).collect();
+//! assert!(export_tasks(&todos).is_ok());
+//! ```
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::table::{Todo, TodoStatus};
+
+const METADATA_PREFIX: &str = "taskwarrior:";
+const TASKWARRIOR_DATE_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+/// A single task as it appears in Taskwarrior's `task export` JSON
+#[derive(Debug, Serialize, Deserialize)]
+struct TaskwarriorTask {
+    uuid: String,
+    description: String,
+    status: String,
+    entry: Option<String>,
+    end: Option<String>,
+    due: Option<String>,
+    annotations: Option<Vec<TaskwarriorAnnotation>>,
+}
+
+/// A single annotation attached to a Taskwarrior task
+#[derive(Debug, Serialize, Deserialize)]
+struct TaskwarriorAnnotation {
+    entry: String,
+    description: String,
+}
+
+/// Imports a Taskwarrior `task export` JSON dump into `Todo`s
+///
+/// # Arguments
+///
+/// * `json` - Raw JSON array as produced by `task export`
+///
+/// # Errors
+///
+/// Returns an error if the JSON can't be parsed, or if any task has a
+/// status other than `pending`/`waiting`/`recurring`/`completed`/`deleted`
+pub fn import_tasks(json: &str) -> Result<Vec<Todo>, String> {
+    let tasks: Vec<TaskwarriorTask> =
+        serde_json::from_str(json).map_err(|e| format!("invalid Taskwarrior JSON: {}", e))?;
+    tasks.into_iter().map(task_to_todo).collect()
+}
+
+/// Exports a list's todos as Taskwarrior `task import` compatible JSON
+///
+/// # Arguments
+///
+/// * `todos` - `(id, &Todo)` pairs to export, as returned by `Table::list_todos`
+///
+/// # Errors
+///
+/// Returns an error if the resulting JSON can't be serialized
+pub fn export_tasks(todos: &[(Uuid, &Todo)]) -> Result<String, String> {
+    let tasks: Vec<TaskwarriorTask> = todos.iter().map(|(_, todo)| todo_to_task(todo)).collect();
+    serde_json::to_string_pretty(&tasks).map_err(|e| format!("failed to build Taskwarrior JSON: {}", e))
+}
+
+/// Converts a single Taskwarrior task into a `Todo`
+fn task_to_todo(task: TaskwarriorTask) -> Result<Todo, String> {
+    let status = match task.status.as_str() {
+        "pending" | "waiting" | "recurring" => TodoStatus::Unfinished,
+        "completed" => TodoStatus::Finished,
+        "deleted" => TodoStatus::Forgave,
+        other => return Err(format!("unknown Taskwarrior status '{}'", other)),
+    };
+
+    let mut description = format!("{}{}", METADATA_PREFIX, task.uuid);
+    if let Some(annotations) = &task.annotations {
+        for annotation in annotations {
+            description.push_str(&format!("\n- {}", annotation.description));
+        }
+    }
+
+    let mut todo = Todo::new(task.description, description);
+    todo.status = status;
+    if let Some(entry) = &task.entry {
+        todo.created = parse_tw_date(entry)?;
+    }
+    if let Some(due) = &task.due {
+        todo.due = Some(parse_tw_date(due)?);
+    }
+    Ok(todo)
+}
+
+/// Converts a single `Todo` into a Taskwarrior task
+fn todo_to_task(todo: &Todo) -> TaskwarriorTask {
+    let (uuid, annotations) = split_metadata(&todo.description);
+    let status = match todo.status {
+        TodoStatus::Unfinished => "pending",
+        TodoStatus::Finished => "completed",
+        TodoStatus::Forgave => "deleted",
+    }
+    .to_string();
+    let end = match todo.status {
+        TodoStatus::Unfinished => None,
+        TodoStatus::Finished | TodoStatus::Forgave => Some(format_tw_date(Utc::now())),
+    };
+
+    TaskwarriorTask {
+        uuid,
+        description: todo.title.clone(),
+        status,
+        entry: Some(format_tw_date(todo.created)),
+        end,
+        due: todo.due.map(format_tw_date),
+        annotations: (!annotations.is_empty()).then_some(annotations),
+    }
+}
+
+/// Splits a rtodo description back into a Taskwarrior uuid and annotations
+///
+/// Descriptions produced by `task_to_todo` start with `taskwarrior:<uuid>`
+/// followed by one `- <text>` line per annotation. Descriptions without
+/// that prefix came from a todo created directly in rtodo; they're given a
+/// fresh uuid and folded whole into a single annotation so the text isn't
+/// lost.
+fn split_metadata(description: &str) -> (String, Vec<TaskwarriorAnnotation>) {
+    if let Some(rest) = description.strip_prefix(METADATA_PREFIX) {
+        let mut lines = rest.lines();
+        let uuid = lines.next().unwrap_or_default().to_string();
+        let annotations = lines
+            .filter_map(|line| line.strip_prefix("- "))
+            .map(|text| TaskwarriorAnnotation {
+                entry: format_tw_date(Utc::now()),
+                description: text.to_string(),
+            })
+            .collect();
+        (uuid, annotations)
+    } else if description.is_empty() {
+        (uuid::Uuid::new_v4().to_string(), Vec::new())
+    } else {
+        let annotation = TaskwarriorAnnotation {
+            entry: format_tw_date(Utc::now()),
+            description: description.to_string(),
+        };
+        (uuid::Uuid::new_v4().to_string(), vec![annotation])
+    }
+}
+
+/// Parses a Taskwarrior date (`%Y%m%dT%H%M%SZ`) into a `DateTime<Utc>`
+fn parse_tw_date(input: &str) -> Result<DateTime<Utc>, String> {
+    NaiveDateTime::parse_from_str(input, TASKWARRIOR_DATE_FORMAT)
+        .map(|naive| naive.and_utc())
+        .map_err(|e| format!("invalid Taskwarrior date '{}': {}", input, e))
+}
+
+/// Formats a `DateTime<Utc>` as a Taskwarrior date (`%Y%m%dT%H%M%SZ`)
+fn format_tw_date(date: DateTime<Utc>) -> String {
+    date.format(TASKWARRIOR_DATE_FORMAT).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_maps_fields() {
+        let json = r#"[{
+            "uuid": "b1b1b1b1-0000-0000-0000-000000000001",
+            "description": "Pay rent",
+            "status": "pending",
+            "due": "20240601T235900Z"
+        }]"#;
+        let todos = import_tasks(json).unwrap();
+        assert_eq!(todos.len(), 1);
+        assert_eq!(todos[0].title, "Pay rent");
+        assert_eq!(todos[0].status, TodoStatus::Unfinished);
+        assert!(todos[0].description.starts_with("taskwarrior:b1b1b1b1"));
+        assert!(todos[0].due.is_some());
+    }
+
+    #[test]
+    fn test_import_status_mapping() {
+        let statuses = [
+            ("pending", TodoStatus::Unfinished),
+            ("waiting", TodoStatus::Unfinished),
+            ("recurring", TodoStatus::Unfinished),
+            ("completed", TodoStatus::Finished),
+            ("deleted", TodoStatus::Forgave),
+        ];
+        for (status, expected) in statuses {
+            let json = format!(
+                r#"[{{"uuid":"u","description":"t","status":"{}"}}]"#,
+                status
+            );
+            let todos = import_tasks(&json).unwrap();
+            assert_eq!(todos[0].status, expected);
+        }
+    }
+
+    #[test]
+    fn test_import_unknown_status_errors() {
+        let json = r#"[{"uuid":"u","description":"t","status":"bogus"}]"#;
+        assert!(import_tasks(json).is_err());
+    }
+
+    #[test]
+    fn test_completed_without_end_still_finishes() {
+        let json = r#"[{"uuid":"u","description":"t","status":"completed"}]"#;
+        let todos = import_tasks(json).unwrap();
+        assert_eq!(todos[0].status, TodoStatus::Finished);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let json = r#"[{
+            "uuid": "b1b1b1b1-0000-0000-0000-000000000001",
+            "description": "Pay rent",
+            "status": "pending",
+            "annotations": [{"entry": "20240601T235900Z", "description": "call landlord"}]
+        }]"#;
+        let todos = import_tasks(json).unwrap();
+        let refs: Vec<(Uuid, &Todo)> = todos.iter().map(|t| (t.id, t)).collect();
+        let exported = export_tasks(&refs).unwrap();
+
+        let reimported = import_tasks(&exported).unwrap();
+        assert_eq!(reimported[0].title, "Pay rent");
+        assert!(reimported[0].description.contains("call landlord"));
+    }
+
+    #[test]
+    fn test_export_local_todo_preserves_description_as_annotation() {
+        let todo = Todo::new("Local task".to_string(), "some notes".to_string());
+        let refs = vec![(todo.id, &todo)];
+        let exported = export_tasks(&refs).unwrap();
+        assert!(exported.contains("some notes"));
+    }
+}
@@ -0,0 +1,244 @@
+//! Tdo module
+//!
+//! This module defines the top-level container that owns every named todo
+//! list in the application. Where `Store` tracks the todos (and undo/redo
+//! history) for a single list, `Tdo` tracks a collection of `Store`s keyed
+//! by list name, so users can keep work/home/project todos apart instead
+//! of cramming everything into one table.
+//!
+//! # Examples
+//!
+//! ```
+//! use rtodo::tdo::Tdo;
+//! use rtodo::store::Action;
+//! use rtodo::table::Todo;
+//!
+//! // A freshly created container already has an empty default list
+//! let mut tdo = Tdo::new();
+//!
+//! // Create a new named list and move a todo into it
+//! tdo.create_list("work".to_string()).unwrap();
+//! let id = tdo.get_list_mut(Tdo::DEFAULT_LIST).unwrap()
+//!     .dispatch(Action::Add(Todo::new("Ship release".to_string(), "Cut v1.0".to_string())));
+//! tdo.move_todo(Tdo::DEFAULT_LIST, "work", id).unwrap();
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::store::{Action, Store};
+
+/// Name of the list that always exists and cannot be removed
+pub const DEFAULT_LIST: &str = "default";
+
+/// Top-level container owning every named todo list
+///
+/// # Fields
+///
+/// * `lists` - Map of list name to its `Store`
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Tdo {
+    lists: BTreeMap<String, Store>,
+}
+
+impl Tdo {
+    /// Name of the list that always exists and cannot be removed
+    pub const DEFAULT_LIST: &'static str = DEFAULT_LIST;
+
+    /// Creates a new container with an empty default list
+    ///
+    /// # Returns
+    ///
+    /// New `Tdo` instance containing only the `default` list
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rtodo::tdo::Tdo;
+    ///
+    /// let tdo = Tdo::new();
+    /// assert!(tdo.list_names().contains(&&Tdo::DEFAULT_LIST.to_string()));
+    /// ```
+    pub fn new() -> Tdo {
+        let mut lists = BTreeMap::new();
+        lists.insert(DEFAULT_LIST.to_string(), Store::new());
+        Tdo { lists }
+    }
+
+    /// Creates a new named list
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the list to create
+    ///
+    /// # Returns
+    ///
+    /// Returns `Err` if a list with that name already exists
+    pub fn create_list(&mut self, name: String) -> Result<(), String> {
+        if self.lists.contains_key(&name) {
+            return Err(format!("List '{}' already exists", name));
+        }
+        self.lists.insert(name, Store::new());
+        Ok(())
+    }
+
+    /// Removes a named list
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the list to remove
+    ///
+    /// # Returns
+    ///
+    /// Returns the removed `Store` on success, `Err` if the list is the
+    /// default list or does not exist
+    pub fn remove_list(&mut self, name: &str) -> Result<Store, String> {
+        if name == DEFAULT_LIST {
+            return Err("Cannot remove the default list".to_string());
+        }
+        self.lists
+            .remove(name)
+            .ok_or_else(|| format!("List '{}' not found", name))
+    }
+
+    /// Renames a named list
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Current name of the list
+    /// * `new_name` - New name for the list
+    ///
+    /// # Returns
+    ///
+    /// Returns `Err` if `name` is the default list or does not exist, or
+    /// `new_name` is already taken
+    pub fn rename_list(&mut self, name: &str, new_name: String) -> Result<(), String> {
+        if name == DEFAULT_LIST {
+            return Err("Cannot rename the default list".to_string());
+        }
+        if self.lists.contains_key(&new_name) {
+            return Err(format!("List '{}' already exists", new_name));
+        }
+        let store = self
+            .lists
+            .remove(name)
+            .ok_or_else(|| format!("List '{}' not found", name))?;
+        self.lists.insert(new_name, store);
+        Ok(())
+    }
+
+    /// Moves a todo from one list to another by id
+    ///
+    /// Dispatches a `Remove` on the source list and an `Add` on the
+    /// destination list, so the move shows up in each list's own undo
+    /// history.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - Name of the list currently containing the todo
+    /// * `to` - Name of the destination list
+    /// * `id` - Id of the todo within `from`
+    ///
+    /// # Returns
+    ///
+    /// Returns the todo's id on success (unchanged, since a todo's `Uuid`
+    /// travels with it), `Err` if either list is missing or the todo isn't
+    /// found in `from`
+    pub fn move_todo(&mut self, from: &str, to: &str, id: Uuid) -> Result<Uuid, String> {
+        if !self.lists.contains_key(to) {
+            return Err(format!("List '{}' not found", to));
+        }
+        let todo = self
+            .lists
+            .get(from)
+            .ok_or_else(|| format!("List '{}' not found", from))?
+            .state()
+            .get_todo(id)
+            .cloned()
+            .ok_or_else(|| format!("Todo '{}' not found in list '{}'", id, from))?;
+
+        self.lists.get_mut(from).unwrap().dispatch(Action::Remove(id));
+        Ok(self.lists.get_mut(to).unwrap().dispatch(Action::Add(todo)))
+    }
+
+    /// Lists the names of all lists
+    ///
+    /// # Returns
+    ///
+    /// Vector of list name references, in name order
+    pub fn list_names(&self) -> Vec<&String> {
+        self.lists.keys().collect()
+    }
+
+    /// Gets an immutable reference to a named list
+    pub fn get_list(&self, name: &str) -> Option<&Store> {
+        self.lists.get(name)
+    }
+
+    /// Gets a mutable reference to a named list
+    pub fn get_list_mut(&mut self, name: &str) -> Option<&mut Store> {
+        self.lists.get_mut(name)
+    }
+}
+
+impl Default for Tdo {
+    fn default() -> Self {
+        Tdo::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::Todo;
+
+    #[test]
+    fn test_new_has_default_list() {
+        let tdo = Tdo::new();
+        assert_eq!(tdo.list_names(), vec![&DEFAULT_LIST.to_string()]);
+    }
+
+    #[test]
+    fn test_create_and_remove_list() {
+        let mut tdo = Tdo::new();
+        tdo.create_list("work".to_string()).unwrap();
+        assert!(tdo.get_list("work").is_some());
+
+        tdo.remove_list("work").unwrap();
+        assert!(tdo.get_list("work").is_none());
+    }
+
+    #[test]
+    fn test_cannot_remove_default_list() {
+        let mut tdo = Tdo::new();
+        assert!(tdo.remove_list(DEFAULT_LIST).is_err());
+    }
+
+    #[test]
+    fn test_rename_list() {
+        let mut tdo = Tdo::new();
+        tdo.create_list("work".to_string()).unwrap();
+        tdo.rename_list("work", "job".to_string()).unwrap();
+        assert!(tdo.get_list("work").is_none());
+        assert!(tdo.get_list("job").is_some());
+    }
+
+    #[test]
+    fn test_move_todo() {
+        let mut tdo = Tdo::new();
+        tdo.create_list("work".to_string()).unwrap();
+        let id = tdo
+            .get_list_mut(DEFAULT_LIST)
+            .unwrap()
+            .dispatch(Action::Add(Todo::new(
+                "title".to_string(),
+                "description".to_string(),
+            )));
+
+        let new_id = tdo.move_todo(DEFAULT_LIST, "work", id).unwrap();
+        assert!(tdo.get_list(DEFAULT_LIST).unwrap().state().get_todo(id).is_none());
+        assert!(tdo.get_list("work").unwrap().state().get_todo(new_id).is_some());
+    }
+}
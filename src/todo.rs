@@ -1,16 +0,0 @@
-use serde::Serialize;
-
-#[derive(Debug,Serialize)]
-pub struct Todo {
-    pub title: String,
-    pub finished: bool
-}
-
-impl Todo {
-    pub fn new(title: &str) -> Todo {
-        Todo {
-            title: title.to_string(),
-            finished: false
-        }
-    }
-}
\ No newline at end of file